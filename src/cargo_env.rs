@@ -0,0 +1,100 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::errors::*;
+
+/// A single entry of a Cargo `[env]` table, with the config file it was
+/// declared in so that `relative` paths can be resolved.
+#[derive(Debug)]
+pub struct CargoEnv {
+    pub key: String,
+    pub value: String,
+    pub force: bool,
+    pub relative: bool,
+    /// Directory `relative` values are resolved against: the parent of the
+    /// `.cargo` directory that declared this entry.
+    pub base: PathBuf,
+}
+
+impl CargoEnv {
+    /// Resolves the value that should be emitted, applying Cargo's precedence
+    /// rules. Returns `None` when the variable is already present in the
+    /// process environment and the entry is not forced.
+    pub fn resolve(&self) -> Option<String> {
+        if !self.force && env::var_os(&self.key).is_some() {
+            return None;
+        }
+
+        if self.relative {
+            let joined = self.base.join(&self.value);
+            Some(joined.to_string_lossy().into_owned())
+        } else {
+            Some(self.value.clone())
+        }
+    }
+}
+
+/// Reads the `[env]` table from the nearest `.cargo/config.toml` (falling back
+/// to the extension-less `.cargo/config`) by searching upwards from
+/// `directory`, mirroring Cargo's own discovery.
+pub fn read(directory: &Path) -> Result<Vec<CargoEnv>> {
+    let mut dir = Some(directory);
+    while let Some(current) = dir {
+        for name in ["config.toml", "config"] {
+            let path = current.join(".cargo").join(name);
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => return parse(&contents, current),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(Error::Access { path, source: err }),
+            }
+        }
+        dir = current.parent();
+    }
+
+    Ok(Vec::new())
+}
+
+fn parse(contents: &str, config_dir: &Path) -> Result<Vec<CargoEnv>> {
+    let value: toml::Value = contents
+        .parse()
+        .map_err(|err: toml::de::Error| Error::LineParse(err.to_string(), 0))?;
+
+    let table = match value.get("env").and_then(toml::Value::as_table) {
+        Some(table) => table,
+        None => return Ok(Vec::new()),
+    };
+
+    // Cargo resolves relative `[env]` paths against the parent of the `.cargo`
+    // directory (the project root), not the `.cargo` directory itself.
+    let base = config_dir.to_path_buf();
+    let mut entries = Vec::with_capacity(table.len());
+    for (key, entry) in table {
+        let (value, force, relative) = match entry {
+            toml::Value::String(value) => (value.clone(), false, false),
+            toml::Value::Table(inner) => {
+                let value = inner
+                    .get("value")
+                    .and_then(toml::Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let force = inner.get("force").and_then(toml::Value::as_bool).unwrap_or(false);
+                let relative = inner
+                    .get("relative")
+                    .and_then(toml::Value::as_bool)
+                    .unwrap_or(false);
+                (value, force, relative)
+            }
+            _ => continue,
+        };
+
+        entries.push(CargoEnv {
+            key: key.clone(),
+            value,
+            force,
+            relative,
+            base: base.clone(),
+        });
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,80 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::errors::*;
+
+/// Options for generating a Rust source file of `pub const` definitions.
+///
+/// The generated file is written into `OUT_DIR` and is meant to be pulled in
+/// with `include!(concat!(env!("OUT_DIR"), "/env.rs"))`.
+#[derive(Debug, Clone)]
+pub struct CodegenOptions<'a> {
+    /// The filename written into `OUT_DIR`. Defaults to `env.rs`.
+    pub filename: &'a str,
+    /// An optional module name to wrap the constants in. When `None` the
+    /// constants are emitted at the top level of the included file.
+    pub module: Option<&'a str>,
+}
+
+impl<'a> Default for CodegenOptions<'a> {
+    fn default() -> Self {
+        CodegenOptions {
+            filename: "env.rs",
+            module: None,
+        }
+    }
+}
+
+/// Writes the `pub const` definitions for `pairs` into `OUT_DIR`.
+pub fn write(options: &CodegenOptions, pairs: &[(String, String)]) -> Result<()> {
+    let out_dir = env::var_os("OUT_DIR").ok_or_else(|| {
+        Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "OUT_DIR is not set; codegen is only available inside a build script",
+        ))
+    })?;
+
+    let mut source = String::new();
+    let indent = if options.module.is_some() { "    " } else { "" };
+    if let Some(module) = options.module {
+        source.push_str(&format!("pub mod {} {{\n", module));
+    }
+    for (key, value) in pairs {
+        source.push_str(&format!(
+            "{}pub const {}: &str = {:?};\n",
+            indent,
+            const_name(key),
+            value
+        ));
+    }
+    if options.module.is_some() {
+        source.push_str("}\n");
+    }
+
+    let path = PathBuf::from(out_dir).join(options.filename);
+    fs::write(&path, source).map_err(Error::Io)
+}
+
+/// Turns an environment key into a valid, convention-abiding Rust constant
+/// name: uppercased (so it doesn't trip `non_upper_case_globals`), with every
+/// character that isn't a letter, digit or underscore replaced by `_`, and a
+/// leading `_` prepended if the first character is a digit.
+fn const_name(key: &str) -> String {
+    let mut name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if name.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    name
+}
@@ -0,0 +1,86 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::result;
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// A line of a dotenv file could not be parsed. Produced by the parser,
+    /// which only knows the 1-based line number; use [`Error::with_path`] to
+    /// attach the file it came from.
+    LineParse(String, usize),
+    /// A parse failure enriched with the file it occurred in.
+    Parse {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    /// A config file could not be accessed for a reason other than it not
+    /// existing (e.g. `PermissionDenied`). We must not silently treat this as
+    /// "not found", since that would hide a real misconfiguration.
+    Access { path: PathBuf, source: io::Error },
+    Io(io::Error),
+}
+
+impl Error {
+    /// Whether this error means the dotenv file simply wasn't there, as opposed
+    /// to being present but unreadable.
+    pub fn not_found(&self) -> bool {
+        if let Error::Io(ref io_error) = *self {
+            io_error.kind() == io::ErrorKind::NotFound
+        } else {
+            false
+        }
+    }
+
+    /// Attaches `path` to a bare [`Error::LineParse`], turning it into the
+    /// richer [`Error::Parse`]. Other variants are returned unchanged.
+    pub fn with_path(self, path: impl Into<PathBuf>) -> Error {
+        match self {
+            Error::LineParse(message, line) => Error::Parse {
+                path: path.into(),
+                line,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::LineParse(message, line) => {
+                write!(f, "error parsing line {}: {}", line, message)
+            }
+            Error::Parse {
+                path,
+                line,
+                message,
+            } => write!(
+                f,
+                "error parsing {}:{}: {}",
+                path.display(),
+                line,
+                message
+            ),
+            Error::Access { path, source } => {
+                write!(f, "could not access {}: {}", path.display(), source)
+            }
+            Error::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Access { source, .. } => Some(source),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::env;
+
+/// Expands `$VAR` and `${VAR}` references inside a value.
+///
+/// References are resolved first against `local` (variables defined earlier in
+/// the same file, kept in insertion order by the caller) and then against the
+/// process environment via [`std::env::var`]. Unknown variables expand to an
+/// empty string. A backslash-escaped `\$` produces a literal `$` and is never
+/// treated as the start of a reference.
+pub fn expand(value: &str, local: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'$') => {
+                chars.next();
+                out.push('$');
+            }
+            '$' => {
+                let name = match chars.peek() {
+                    Some('{') => {
+                        chars.next();
+                        let mut name = String::new();
+                        for c in chars.by_ref() {
+                            if c == '}' {
+                                break;
+                            }
+                            name.push(c);
+                        }
+                        name
+                    }
+                    _ => {
+                        let mut name = String::new();
+                        while let Some(&c) = chars.peek() {
+                            if c == '_' || c.is_ascii_alphanumeric() {
+                                name.push(c);
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                        name
+                    }
+                };
+
+                if name.is_empty() {
+                    out.push('$');
+                } else {
+                    let resolved = local
+                        .get(&name)
+                        .cloned()
+                        .or_else(|| env::var(&name).ok())
+                        .unwrap_or_default();
+                    out.push_str(&resolved);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
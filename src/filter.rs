@@ -0,0 +1,45 @@
+/// Controls which keys are emitted, via include (allow) and exclude (deny)
+/// lists of glob patterns.
+///
+/// A key passes when it matches at least one include pattern (or the include
+/// list is empty, meaning "all keys") and matches none of the exclude
+/// patterns. Patterns support `*` (any run of characters) and `?` (any single
+/// character); everything else is matched literally.
+#[derive(Debug, Default, Clone)]
+pub struct KeyFilter<'a> {
+    /// Patterns a key must match to be emitted. An empty list allows every key.
+    pub include: Vec<&'a str>,
+    /// Patterns that, when matched, exclude a key even if it was included.
+    pub exclude: Vec<&'a str>,
+}
+
+impl<'a> KeyFilter<'a> {
+    /// Returns whether `key` should be emitted given the include/exclude lists.
+    pub fn allows(&self, key: &str) -> bool {
+        let included = self.include.is_empty()
+            || self.include.iter().any(|pattern| matches(pattern, key));
+        let excluded = self.exclude.iter().any(|pattern| matches(pattern, key));
+        included && !excluded
+    }
+}
+
+/// Matches `text` against a glob `pattern` supporting `*` and `?`.
+fn matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    matches_at(&pattern, &text)
+}
+
+fn matches_at(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => {
+            matches_at(&pattern[1..], text)
+                || (!text.is_empty() && matches_at(pattern, &text[1..]))
+        }
+        Some('?') => !text.is_empty() && matches_at(&pattern[1..], &text[1..]),
+        Some(&c) => {
+            !text.is_empty() && text[0] == c && matches_at(&pattern[1..], &text[1..])
+        }
+    }
+}
@@ -1,26 +1,85 @@
-use std::fs::File;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use std::{env, fs, io};
 
 use crate::errors::*;
 use crate::iter::Iter;
 use crate::Config;
 
-pub fn find(config: &Config) -> Result<(PathBuf, Iter<File>)> {
-    let path = find_internal(
-        &env::current_dir().map_err(Error::Io)?,
-        config.filename,
-        config.recursive_search,
-    )?;
-    let file = File::open(&path).map_err(Error::Io)?;
-    let iter = Iter::new(file);
+/// Lightweight file metadata returned by [`Provider::metadata`], so that
+/// in-memory providers don't have to synthesise a [`std::fs::Metadata`].
+pub struct Metadata {
+    is_file: bool,
+}
+
+impl Metadata {
+    pub fn is_file(&self) -> bool {
+        self.is_file
+    }
+}
+
+/// Abstraction over the filesystem and process environment used by [`find`].
+///
+/// Decoupling the IO mirrors how Starship's `Context`/`Env` are threaded
+/// through, letting the recursive-search logic be exercised deterministically
+/// without touching the real filesystem.
+pub trait Provider {
+    fn current_dir(&self) -> io::Result<PathBuf>;
+    fn metadata(&self, path: &Path) -> io::Result<Metadata>;
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>>;
+    fn var(&self, key: &str) -> Option<String>;
+}
+
+/// The real, OS-backed [`Provider`] used by default.
+pub struct OsProvider;
+
+impl Provider for OsProvider {
+    fn current_dir(&self) -> io::Result<PathBuf> {
+        std::env::current_dir()
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        std::fs::metadata(path).map(|metadata| Metadata {
+            is_file: metadata.is_file(),
+        })
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>> {
+        std::fs::File::open(path).map(|file| Box::new(file) as Box<dyn Read>)
+    }
+
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+}
+
+pub fn find(config: &Config) -> Result<(PathBuf, Iter<Box<dyn Read>>)> {
+    find_with(&OsProvider, config)
+}
+
+pub fn find_with(
+    provider: &dyn Provider,
+    config: &Config,
+) -> Result<(PathBuf, Iter<Box<dyn Read>>)> {
+    let start = match config.start_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => provider.current_dir().map_err(Error::Io)?,
+    };
+
+    let path = find_internal(provider, &start, config.filename, config.recursive_search)?;
+    let reader = provider.open(&path).map_err(Error::Io)?;
+    let iter = Iter::new(reader).expand(config.expand_variables);
     Ok((path, iter))
 }
 
-fn find_internal(directory: &Path, filename: &Path, recursive: bool) -> Result<PathBuf> {
+fn find_internal(
+    provider: &dyn Provider,
+    directory: &Path,
+    filename: &Path,
+    recursive: bool,
+) -> Result<PathBuf> {
     let candidate = directory.join(filename);
 
-    match fs::metadata(&candidate) {
+    match provider.metadata(&candidate) {
         Ok(metadata) => {
             if metadata.is_file() {
                 return Ok(candidate);
@@ -28,16 +87,106 @@ fn find_internal(directory: &Path, filename: &Path, recursive: bool) -> Result<P
         }
         Err(error) => {
             if error.kind() != io::ErrorKind::NotFound {
-                return Err(Error::Io(error));
+                return Err(Error::Access {
+                    path: candidate,
+                    source: error,
+                });
             }
         }
     }
 
     match directory.parent() {
-        Some(parent) if recursive => find_internal(parent, filename, recursive),
+        Some(parent) if recursive => find_internal(provider, parent, filename, recursive),
         _ => Err(Error::Io(io::Error::new(
             io::ErrorKind::NotFound,
             "path not found",
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// An in-memory [`Provider`] backed by a map of path to file contents.
+    struct MemoryProvider {
+        cwd: PathBuf,
+        files: HashMap<PathBuf, String>,
+        vars: HashMap<String, String>,
+    }
+
+    impl MemoryProvider {
+        fn new(cwd: &str, files: &[(&str, &str)]) -> Self {
+            MemoryProvider {
+                cwd: PathBuf::from(cwd),
+                files: files
+                    .iter()
+                    .map(|(path, contents)| (PathBuf::from(path), contents.to_string()))
+                    .collect(),
+                vars: HashMap::new(),
+            }
+        }
+    }
+
+    impl Provider for MemoryProvider {
+        fn current_dir(&self) -> io::Result<PathBuf> {
+            Ok(self.cwd.clone())
+        }
+
+        fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+            if self.files.contains_key(path) {
+                Ok(Metadata { is_file: true })
+            } else {
+                Err(io::Error::new(io::ErrorKind::NotFound, "path not found"))
+            }
+        }
+
+        fn open(&self, path: &Path) -> io::Result<Box<dyn Read>> {
+            match self.files.get(path) {
+                Some(contents) => Ok(Box::new(io::Cursor::new(contents.clone().into_bytes()))),
+                None => Err(io::Error::new(io::ErrorKind::NotFound, "path not found")),
+            }
+        }
+
+        fn var(&self, key: &str) -> Option<String> {
+            self.vars.get(key).cloned()
+        }
+    }
+
+    #[test]
+    fn finds_file_in_current_directory() {
+        let provider = MemoryProvider::new("/project/crate", &[("/project/crate/.env", "A=1")]);
+        let path =
+            find_internal(&provider, Path::new("/project/crate"), Path::new(".env"), true).unwrap();
+        assert_eq!(path, PathBuf::from("/project/crate/.env"));
+    }
+
+    #[test]
+    fn recursive_search_walks_up_to_ancestor() {
+        let provider = MemoryProvider::new("/project/crate", &[("/project/.env", "A=1")]);
+        let path =
+            find_internal(&provider, Path::new("/project/crate"), Path::new(".env"), true).unwrap();
+        assert_eq!(path, PathBuf::from("/project/.env"));
+    }
+
+    #[test]
+    fn non_recursive_search_does_not_walk_up() {
+        let provider = MemoryProvider::new("/project/crate", &[("/project/.env", "A=1")]);
+        let result =
+            find_internal(&provider, Path::new("/project/crate"), Path::new(".env"), false);
+        assert!(result.unwrap_err().not_found());
+    }
+
+    #[test]
+    fn start_dir_overrides_current_dir() {
+        let provider = MemoryProvider::new("/elsewhere", &[("/project/.env", "A=1")]);
+        let config = Config {
+            start_dir: Some(Path::new("/project")),
+            ..Default::default()
+        };
+        let (path, _) = find_with(&provider, &config).unwrap();
+        assert_eq!(path, PathBuf::from("/project/.env"));
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+use crate::errors::*;
+use crate::parse;
+
+/// Iterator over the `(key, value)` pairs of a dotenv file.
+///
+/// Keys seen earlier in the file are retained so that later values can
+/// reference them when expansion is enabled via [`Iter::expand`].
+pub struct Iter<R> {
+    lines: std::io::Lines<BufReader<R>>,
+    substitution_data: HashMap<String, String>,
+    expand: bool,
+    line_number: usize,
+}
+
+impl<R: Read> Iter<R> {
+    pub fn new(reader: R) -> Iter<R> {
+        Iter {
+            lines: BufReader::new(reader).lines(),
+            substitution_data: HashMap::new(),
+            expand: false,
+            line_number: 0,
+        }
+    }
+
+    /// Enables `$VAR`/`${VAR}` expansion of double-quoted and unquoted values.
+    pub fn expand(mut self, expand: bool) -> Iter<R> {
+        self.expand = expand;
+        self
+    }
+}
+
+impl<R: Read> Iterator for Iter<R> {
+    type Item = Result<(String, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(err) => return Some(Err(Error::Io(err))),
+            };
+            self.line_number += 1;
+
+            match parse::parse_line(&line, &self.substitution_data, self.expand) {
+                Ok(Some((key, value))) => {
+                    if self.expand {
+                        self.substitution_data.insert(key.clone(), value.clone());
+                    }
+                    return Some(Ok((key, value)));
+                }
+                Ok(None) => continue,
+                Err(Error::LineParse(message, _)) => {
+                    return Some(Err(Error::LineParse(message, self.line_number)))
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
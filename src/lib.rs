@@ -80,11 +80,19 @@
 //! dotenv_build::output_multiple(configs).unwrap();
 //! ```
 
+mod cargo_env;
+mod codegen;
 mod errors;
+mod expand;
+mod filter;
 mod find;
 mod iter;
 mod parse;
 
+pub use crate::codegen::CodegenOptions;
+pub use crate::filter::KeyFilter;
+
+use std::env;
 use std::io;
 use std::io::Write;
 use std::path::Path;
@@ -101,6 +109,35 @@ pub struct Config<'a> {
     pub recursive_search: bool,
     /// This specifies if we should return an error if we don't find the file. Defaults to `false`.
     pub fail_if_missing_dotenv: bool,
+    /// This specifies if values may reference other variables via `$VAR` or `${VAR}`, resolving
+    /// first against variables defined earlier in the same file and then against the process
+    /// environment. Single-quoted values are emitted verbatim, double-quoted and unquoted values
+    /// are expanded, and `\$` yields a literal `$`. Defaults to `false`.
+    pub expand_variables: bool,
+    /// This specifies if the nearest `.cargo/config.toml` `[env]` table should be read and merged
+    /// with the `.env` values. Entries follow Cargo's precedence: an entry does not override a
+    /// variable already present in the process environment unless `force = true`, and `relative`
+    /// entries are resolved against the directory that contains the `.cargo` directory. Defaults
+    /// to `false`.
+    pub cargo_env: bool,
+    /// When set, a Rust source file of `pub const` definitions for each key is written into
+    /// `OUT_DIR`, to be pulled in with `include!(concat!(env!("OUT_DIR"), "/env.rs"))`. This is
+    /// emitted in addition to the `cargo:rustc-env` lines. Defaults to `None`.
+    pub codegen: Option<CodegenOptions<'a>>,
+    /// Overrides the directory the upward recursive search starts from. When `None` the build
+    /// script's current directory is used. Useful in workspaces where the build script's CWD
+    /// isn't the crate root. Defaults to `None`.
+    pub start_dir: Option<&'a Path>,
+    /// This specifies if a `cargo:rerun-if-env-changed=KEY` line should be emitted for every
+    /// variable set, so that changing the value in the ambient process environment retriggers the
+    /// build. Defaults to `false`.
+    pub rerun_if_env_changed: bool,
+    /// An optional prefix applied to every emitted key (e.g. `MYAPP_`) to avoid collisions with
+    /// unrelated crates. Filtering is applied to the original, unprefixed key. Defaults to `None`.
+    pub prefix: Option<&'a str>,
+    /// Include/exclude filter restricting which keys of a shared `.env` are exposed to the
+    /// compilation. Defaults to allowing every key.
+    pub filter: KeyFilter<'a>,
 }
 
 impl<'a> Default for Config<'a> {
@@ -109,6 +146,13 @@ impl<'a> Default for Config<'a> {
             filename: Path::new(".env"),
             recursive_search: true,
             fail_if_missing_dotenv: false,
+            expand_variables: false,
+            cargo_env: false,
+            codegen: None,
+            start_dir: None,
+            rerun_if_env_changed: false,
+            prefix: None,
+            filter: KeyFilter::default(),
         }
     }
 }
@@ -195,21 +239,85 @@ where
                 Ok(())
             };
         }
-        Err(err) => return Err(err),
+        Err(err) => {
+            writeln!(stdout, "cargo:warning={}", err)?;
+            return Err(err);
+        }
     };
 
+    let mut seen = std::collections::HashSet::new();
+    let mut pairs = Vec::new();
     for line in lines {
         let (key, value) = match line {
             Ok(l) => l,
             Err(err) => {
-                eprintln!("[dotenv-build] {}", err);
+                let err = err.with_path(&path);
+                writeln!(stdout, "cargo:warning={}", err)?;
                 return Err(err);
             }
         };
 
-        writeln!(stdout, "cargo:rustc-env={}={}", key, value)?;
+        seen.insert(key.clone());
+        emit(stdout, &config, &key, value, &mut pairs)?;
+    }
+
+    if config.cargo_env {
+        let start = env::current_dir().map_err(Error::Io)?;
+        let entries = match cargo_env::read(&start) {
+            Ok(entries) => entries,
+            Err(err) => {
+                writeln!(stdout, "cargo:warning={}", err)?;
+                return Err(err);
+            }
+        };
+        for entry in entries {
+            if seen.contains(&entry.key) {
+                continue;
+            }
+            if let Some(value) = entry.resolve() {
+                seen.insert(entry.key.clone());
+                emit(stdout, &config, &entry.key, value, &mut pairs)?;
+            }
+        }
+    }
+
+    if let Some(options) = &config.codegen {
+        codegen::write(options, &pairs)?;
     }
 
     writeln!(stdout, "cargo:rerun-if-changed={}", path.to_str().unwrap())?;
     Ok(())
 }
+
+/// Emits a single variable, honoring the configured filter, prefix and
+/// `rerun-if-env-changed` options, and records it for codegen.
+fn emit<T>(
+    stdout: &mut T,
+    config: &Config,
+    key: &str,
+    value: String,
+    pairs: &mut Vec<(String, String)>,
+) -> Result<()>
+where
+    T: Write,
+{
+    if !config.filter.allows(key) {
+        return Ok(());
+    }
+
+    // Watch the original, unprefixed name: that is the variable actually read
+    // from the ambient environment (by `cargo_env` and expansion), so it is the
+    // one whose change should retrigger the build.
+    if config.rerun_if_env_changed {
+        writeln!(stdout, "cargo:rerun-if-env-changed={}", key)?;
+    }
+
+    let key = match config.prefix {
+        Some(prefix) => format!("{}{}", prefix, key),
+        None => key.to_string(),
+    };
+
+    writeln!(stdout, "cargo:rustc-env={}={}", key, value)?;
+    pairs.push((key, value));
+    Ok(())
+}
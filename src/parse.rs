@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use crate::errors::*;
+use crate::expand;
+
+/// Parses a single line into a `(key, value)` pair.
+///
+/// Blank lines and comments (`#`) yield `None`. Quoting is honored: single
+/// quotes are emitted verbatim with no expansion or escape processing, while
+/// double-quoted and unquoted values have `\$` collapsed to a literal `$` and,
+/// when `expand` is set, `$VAR`/`${VAR}` references substituted against
+/// `substitution_data` (keys seen earlier in the same file) and then the
+/// process environment.
+pub fn parse_line(
+    line: &str,
+    substitution_data: &HashMap<String, String>,
+    expand: bool,
+) -> Result<Option<(String, String)>> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return Ok(None);
+    }
+
+    let trimmed = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+
+    let (key, rest) = match trimmed.split_once('=') {
+        Some((key, rest)) => (key.trim(), rest),
+        None => {
+            return Err(Error::LineParse(line.to_owned(), 0));
+        }
+    };
+
+    if key.is_empty() {
+        return Err(Error::LineParse(line.to_owned(), 0));
+    }
+
+    let value = parse_value(rest, substitution_data, expand);
+    Ok(Some((key.to_owned(), value)))
+}
+
+fn parse_value(
+    raw: &str,
+    substitution_data: &HashMap<String, String>,
+    expand: bool,
+) -> String {
+    let trimmed = raw.trim();
+
+    if let Some(inner) = strip_matching(trimmed, '\'') {
+        // Single quotes: verbatim, never expanded.
+        return inner.to_owned();
+    }
+
+    let unquoted = strip_matching(trimmed, '"').unwrap_or(trimmed);
+    if expand {
+        expand::expand(unquoted, substitution_data)
+    } else {
+        unescape_dollar(unquoted)
+    }
+}
+
+/// Returns the contents of `value` with the surrounding `quote` characters
+/// stripped, or `None` when it is not wrapped in a matching pair.
+fn strip_matching(value: &str, quote: char) -> Option<&str> {
+    let bytes = value.as_bytes();
+    if value.len() >= 2 && bytes[0] == quote as u8 && bytes[value.len() - 1] == quote as u8 {
+        Some(&value[1..value.len() - 1])
+    } else {
+        None
+    }
+}
+
+/// Collapses `\$` to a literal `$` without performing any substitution.
+fn unescape_dollar(value: &str) -> String {
+    value.replace("\\$", "$")
+}